@@ -0,0 +1,70 @@
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Supplies the header that authenticates a request, called on every
+/// outgoing request so a token can be refreshed out-of-band (e.g. an OAuth
+/// provider that re-mints an expiring token and caches it until near
+/// expiry).
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// The header this provider sets. Defaults to `authorization`.
+    fn header_name(&self) -> &str {
+        "authorization"
+    }
+
+    /// The value to send for [`Self::header_name`], if any.
+    async fn authorization_header(&self) -> Result<Option<String>>;
+
+    /// Called after a `401` response so a cached credential can be dropped
+    /// and re-derived on the next call. Default is a no-op.
+    async fn invalidate(&self) {}
+}
+
+/// A [`CredentialProvider`] that always sends the same bearer token.
+pub struct BearerTokenProvider {
+    token: String,
+}
+
+impl BearerTokenProvider {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self { token: token.into() }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for BearerTokenProvider {
+    async fn authorization_header(&self) -> Result<Option<String>> {
+        Ok(Some(format!("Bearer {}", self.token)))
+    }
+}
+
+/// A [`CredentialProvider`] that sends a static API key via a header
+/// (`x-api-key` by default).
+pub struct ApiKeyProvider {
+    header_name: String,
+    key: String,
+}
+
+impl ApiKeyProvider {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self::with_header_name("x-api-key", key)
+    }
+
+    pub fn with_header_name(header_name: impl Into<String>, key: impl Into<String>) -> Self {
+        Self {
+            header_name: header_name.into(),
+            key: key.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for ApiKeyProvider {
+    fn header_name(&self) -> &str {
+        &self.header_name
+    }
+
+    async fn authorization_header(&self) -> Result<Option<String>> {
+        Ok(Some(self.key.clone()))
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use isahc::http::Method;
+use isahc::{AsyncReadResponseExt, Request, RequestExt};
+
+/// A completed HTTP response, decoupled from whichever HTTP client produced it.
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn new(status: u16, headers: HashMap<String, String>, body: Vec<u8>) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+        }
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    pub fn headers(&self) -> &HashMap<String, String> {
+        &self.headers
+    }
+
+    /// Look up a header by name, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn text(&self) -> Result<String> {
+        Ok(String::from_utf8(self.body.clone())?)
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+/// Abstraction over an HTTP client so `UnstorageClient` is not tied to a single
+/// transport. Implement this to plug in a different HTTP library, or a mock
+/// (see [`MockBackend`]) to exercise the client without a live server.
+pub trait HttpBackend {
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HashMap<String, String>,
+        body: Option<Vec<u8>>,
+    ) -> Result<HttpResponse>;
+}
+
+/// The default backend, built on `isahc`.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct IsahcBackend;
+
+impl HttpBackend for IsahcBackend {
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HashMap<String, String>,
+        body: Option<Vec<u8>>,
+    ) -> Result<HttpResponse> {
+        let mut builder = Request::builder().method(method).uri(url);
+        for (key, value) in &headers {
+            builder = builder.header(key, value);
+        }
+        let request = builder.body(body.unwrap_or_default())?;
+
+        let mut response = request.send_async().await?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_str().unwrap_or_default().to_string()))
+            .collect();
+        let body = response.bytes().await?.to_vec();
+
+        Ok(HttpResponse::new(status, headers, body))
+    }
+}
+
+/// A single call captured by [`MockBackend`], for asserting what was
+/// actually sent rather than just how many responses were consumed.
+#[derive(Debug, Clone)]
+pub struct RecordedRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HashMap<String, String>,
+    pub body: Option<Vec<u8>>,
+}
+
+/// A backend that returns pre-queued responses instead of making network calls.
+///
+/// Useful for unit-testing code built on `UnstorageClient` without a live
+/// unstorage server.
+#[derive(Default)]
+pub struct MockBackend {
+    responses: Mutex<VecDeque<HttpResponse>>,
+    requests: Mutex<Vec<RecordedRequest>>,
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue a response to be returned by the next call to `send`, in FIFO order.
+    pub fn push_response(&self, response: HttpResponse) {
+        self.responses.lock().unwrap().push_back(response);
+    }
+
+    /// Every call made so far, in order, for asserting on outgoing requests
+    /// (e.g. that a retried request carried a refreshed `Authorization`
+    /// header, or that a revalidating GET sent `if-none-match`).
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl HttpBackend for MockBackend {
+    async fn send(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HashMap<String, String>,
+        body: Option<Vec<u8>>,
+    ) -> Result<HttpResponse> {
+        self.requests.lock().unwrap().push(RecordedRequest {
+            method: method.clone(),
+            url: url.to_string(),
+            headers: headers.clone(),
+            body: body.clone(),
+        });
+
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .ok_or_else(|| anyhow!("MockBackend: no queued response"))
+    }
+}
@@ -0,0 +1,243 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+
+use crate::{HttpBackend, IsahcBackend, TransactionOptions, UnstorageClient};
+
+/// How a [`CachedUnstorageClient`] weighs the local cache against the network
+/// on each read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CachePolicy {
+    /// Skip the cache on reads; always hit the network. Writes still update
+    /// the cache so later reads under another policy can use it.
+    Bypass,
+    /// Serve a fresh cache entry without a network round-trip. This is the
+    /// default.
+    #[default]
+    PreferCache,
+    /// Always hit the network first, falling back to the cache only if the
+    /// request fails.
+    NetworkFirst,
+}
+
+struct CacheRow {
+    value: Vec<u8>,
+    mtime: u64,
+    ttl: Option<u64>,
+}
+
+impl CacheRow {
+    fn is_fresh(&self) -> bool {
+        let Some(ttl) = self.ttl else {
+            return true;
+        };
+        let age = now_secs().saturating_sub(self.mtime);
+        age < ttl
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// A write-through SQLite cache layer in front of an [`UnstorageClient`].
+///
+/// Reads consult the local cache first (per the configured [`CachePolicy`]);
+/// writes go to the server and then update the cache. If a network call
+/// fails, the cache is consulted even if the entry is stale, so the caller
+/// still gets a value in degraded/offline conditions.
+///
+/// Only an error returned by the inner client counts as a failed call. A
+/// non-success status (including a 5xx) comes back from the inner client as
+/// a missing item, so it evicts the cached entry and returns `None` instead
+/// of falling back to the cache.
+pub struct CachedUnstorageClient<B: HttpBackend = IsahcBackend> {
+    inner: UnstorageClient<B>,
+    pool: Pool<SqliteConnectionManager>,
+    default_ttl: Duration,
+    cache_policy: CachePolicy,
+}
+
+impl<B: HttpBackend> CachedUnstorageClient<B> {
+    /// Wrap `inner` with a SQLite-backed cache stored at `db_path` (use
+    /// `":memory:"` for an ephemeral cache). `default_ttl` is applied to
+    /// entries populated from the network.
+    pub fn new(
+        inner: UnstorageClient<B>,
+        db_path: &str,
+        default_ttl: Duration,
+        cache_policy: CachePolicy,
+    ) -> Result<Self> {
+        let manager = SqliteConnectionManager::file(db_path);
+        // A single connection: SQLite serializes writers anyway, and a pool
+        // of >1 connections would each see their own separate database when
+        // `db_path` is `":memory:"`.
+        let pool = Pool::builder().max_size(1).build(manager)?;
+        pool.get()?.execute(
+            "CREATE TABLE IF NOT EXISTS cache (
+                key TEXT PRIMARY KEY,
+                value BLOB NOT NULL,
+                mtime INTEGER NOT NULL,
+                ttl INTEGER
+            )",
+            [],
+        )?;
+
+        Ok(Self {
+            inner,
+            pool,
+            default_ttl,
+            cache_policy,
+        })
+    }
+
+    /// Get an item, consulting the cache per the configured [`CachePolicy`].
+    ///
+    /// Errors on invalid UTF-8, matching `UnstorageClient::get_item`.
+    pub async fn get_item(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<Option<String>> {
+        let bytes = self.get_item_raw(key, topts).await?;
+        bytes.map(|b| String::from_utf8(b).map_err(Into::into)).transpose()
+    }
+
+    /// Get an item in binary mode, consulting the cache per the configured
+    /// [`CachePolicy`].
+    pub async fn get_item_raw(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<Option<Vec<u8>>> {
+        if self.cache_policy == CachePolicy::PreferCache {
+            if let Some(row) = self.cache_get(key)? {
+                if row.is_fresh() {
+                    return Ok(Some(row.value));
+                }
+            }
+        }
+
+        match self.inner.get_item_raw(key, topts).await {
+            Ok(Some(bytes)) => {
+                self.cache_put(key, &bytes)?;
+                Ok(Some(bytes))
+            }
+            Ok(None) => {
+                self.cache_delete(key)?;
+                Ok(None)
+            }
+            // Bypass means "always hit the network"; don't mask a network
+            // failure with stale data the caller asked to skip.
+            Err(err) if self.cache_policy == CachePolicy::Bypass => Err(err),
+            Err(err) => match self.cache_get(key)? {
+                Some(row) => Ok(Some(row.value)),
+                None => Err(err),
+            },
+        }
+    }
+
+    /// Set an item, updating the cache once the server confirms the write.
+    pub async fn set_item(&self, key: &str, value: &str, topts: Option<&TransactionOptions>) -> Result<()> {
+        self.inner.set_item(key, value, topts).await?;
+        self.cache_put(key, value.as_bytes())?;
+        Ok(())
+    }
+
+    /// Set an item in binary mode, updating the cache once the server
+    /// confirms the write.
+    pub async fn set_item_raw(&self, key: &str, value: &[u8], topts: Option<&TransactionOptions>) -> Result<()> {
+        self.inner.set_item_raw(key, value, topts).await?;
+        self.cache_put(key, value)?;
+        Ok(())
+    }
+
+    /// Remove an item, evicting it from the cache once the server confirms
+    /// the removal.
+    pub async fn remove_item(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<()> {
+        self.inner.remove_item(key, topts).await?;
+        self.cache_delete(key)?;
+        Ok(())
+    }
+
+    fn cache_get(&self, key: &str) -> Result<Option<CacheRow>> {
+        let conn = self.pool.get()?;
+        match conn.query_row(
+            "SELECT value, mtime, ttl FROM cache WHERE key = ?1",
+            [key],
+            |row| {
+                Ok(CacheRow {
+                    value: row.get(0)?,
+                    mtime: row.get(1)?,
+                    ttl: row.get(2)?,
+                })
+            },
+        ) {
+            Ok(row) => Ok(Some(row)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn cache_put(&self, key: &str, value: &[u8]) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO cache (key, value, mtime, ttl)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, mtime = excluded.mtime, ttl = excluded.ttl",
+            rusqlite::params![key, value, now_secs(), self.default_ttl.as_secs()],
+        )?;
+        Ok(())
+    }
+
+    fn cache_delete(&self, key: &str) -> Result<()> {
+        let conn = self.pool.get()?;
+        conn.execute("DELETE FROM cache WHERE key = ?1", [key])?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HttpResponse, MockBackend, UnstorageClient};
+    use std::collections::HashMap;
+
+    async fn populated_client(cache_policy: CachePolicy) -> CachedUnstorageClient<MockBackend> {
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(200, HashMap::new(), Vec::new()));
+
+        let inner = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend);
+        // ttl=0 keeps the entry permanently stale, so get_item_raw always
+        // attempts the network call (and thus can hit our simulated failure)
+        // instead of short-circuiting on a still-fresh cache hit.
+        let client = CachedUnstorageClient::new(inner, ":memory:", Duration::from_secs(0), cache_policy).unwrap();
+
+        // The populating set_item below consumes the only queued response,
+        // so the next network call fails, simulating an offline/degraded
+        // server.
+        client.set_item("key", "hello", None).await.unwrap();
+        client
+    }
+
+    #[tokio::test]
+    async fn test_get_item_raw_stale_on_error_for_prefer_cache() {
+        let client = populated_client(CachePolicy::PreferCache).await;
+        let value = client.get_item_raw("key", None).await.unwrap();
+        assert_eq!(value, Some(b"hello".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_get_item_raw_bypass_propagates_error() {
+        let client = populated_client(CachePolicy::Bypass).await;
+        assert!(client.get_item_raw("key", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_item_errors_on_invalid_utf8() {
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(200, HashMap::new(), vec![0xff, 0xfe]));
+        let inner = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend);
+        let client =
+            CachedUnstorageClient::new(inner, ":memory:", Duration::from_secs(60), CachePolicy::NetworkFirst).unwrap();
+
+        assert!(client.get_item("key", None).await.is_err());
+    }
+}
@@ -1,25 +1,113 @@
-use isahc::{AsyncReadResponseExt, Request, RequestExt};
+mod auth;
+mod backend;
+mod cache;
+mod retry;
+
+pub use auth::{ApiKeyProvider, BearerTokenProvider, CredentialProvider};
+pub use backend::{HttpBackend, HttpResponse, IsahcBackend, MockBackend, RecordedRequest};
+pub use cache::{CachePolicy, CachedUnstorageClient};
+pub use retry::{ClientOptions, RetryPolicy};
+
 use anyhow::Result;
+use isahc::http::Method;
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 use chrono::{DateTime, Utc};
-use isahc::http::HeaderName;
 use serde::{Serialize, de::DeserializeOwned};
 
-pub struct UnstorageClient {
+/// What a client remembers about the last successful fetch of a key, so the
+/// next GET can be a conditional request instead of a full re-download.
+#[derive(Debug, Clone)]
+struct RevalidationEntry {
+    value: Vec<u8>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: SystemTime,
+    max_age: Option<Duration>,
+    no_store: bool,
+}
+
+/// Parse the `max-age` and `no-store` directives out of a `Cache-Control` header.
+fn parse_cache_control(header: Option<&str>) -> (Option<Duration>, bool) {
+    let Some(header) = header else {
+        return (None, false);
+    };
+
+    let mut max_age = None;
+    let mut no_store = false;
+    for directive in header.split(',').map(str::trim) {
+        if directive.eq_ignore_ascii_case("no-store") {
+            no_store = true;
+        } else if let Some(value) = directive
+            .to_ascii_lowercase()
+            .strip_prefix("max-age=")
+            .and_then(|s| s.parse::<u64>().ok())
+        {
+            max_age = Some(Duration::from_secs(value));
+        }
+    }
+    (max_age, no_store)
+}
+
+pub struct UnstorageClient<B: HttpBackend = IsahcBackend> {
     base_url: String,
     headers: HashMap<String, String>,
+    backend: B,
+    options: ClientOptions,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
+    invalidate_credential_on_401: bool,
+    revalidation_cache: Mutex<HashMap<String, RevalidationEntry>>,
 }
 
-impl UnstorageClient {
+impl UnstorageClient<IsahcBackend> {
     /// Create a new Unstorage client with the given base URL and custom headers.
     pub fn new(base_url: String, headers: Option<HashMap<String, String>>) -> Self {
+        Self::with_backend(base_url, headers, IsahcBackend)
+    }
+}
+
+impl<B: HttpBackend> UnstorageClient<B> {
+    /// Create a new Unstorage client backed by a custom [`HttpBackend`], e.g. to
+    /// swap in a different HTTP library or a [`MockBackend`] for tests.
+    pub fn with_backend(base_url: String, headers: Option<HashMap<String, String>>, backend: B) -> Self {
         Self {
             base_url,
             headers: headers.unwrap_or_default(),
+            backend,
+            options: ClientOptions::default(),
+            credential_provider: None,
+            invalidate_credential_on_401: false,
+            revalidation_cache: Mutex::new(HashMap::new()),
         }
     }
 
+    /// Set the [`ClientOptions`] (e.g. retry policy) for this client.
+    pub fn with_options(mut self, options: ClientOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Set the retry policy for this client; shorthand for
+    /// `with_options(ClientOptions { retry: Some(policy), .. })`.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.options.retry = Some(policy);
+        self
+    }
+
+    /// Authenticate every request via `provider`. If `invalidate_on_401` is
+    /// set, a `401` response invalidates the cached credential and the
+    /// request is retried once.
+    pub fn with_credential_provider(
+        mut self,
+        provider: impl CredentialProvider + 'static,
+        invalidate_on_401: bool,
+    ) -> Self {
+        self.credential_provider = Some(Arc::new(provider));
+        self.invalidate_credential_on_401 = invalidate_on_401;
+        self
+    }
+
     /// Get headers for a request, including transaction options.
     fn get_headers(&self, topts: Option<&TransactionOptions>) -> HashMap<String, String> {
         let mut headers = self.headers.clone();
@@ -34,38 +122,102 @@ impl UnstorageClient {
         headers
     }
 
-    /// Helper function to add headers to a request.
-    fn add_headers_to_request<R>(&self, request: &mut Request<R>, topts: Option<&TransactionOptions>) -> Result<()> {
-        for (key, value) in self.get_headers(topts) {
-            let header_name = HeaderName::from_bytes(key.as_bytes())?;
-            request.headers_mut().insert(header_name, value.parse()?);
+    /// Ask the configured [`CredentialProvider`], if any, for its header and
+    /// add it to `headers`.
+    async fn apply_credentials(&self, headers: &mut HashMap<String, String>) -> Result<()> {
+        if let Some(provider) = &self.credential_provider {
+            if let Some(value) = provider.authorization_header().await? {
+                headers.insert(provider.header_name().to_string(), value);
+            }
         }
         Ok(())
     }
 
+    /// Send a request, retrying per the configured [`RetryPolicy`] when
+    /// `retry_eligible` is true. Callers pass true for idempotent operations,
+    /// and for `set_item*` only when the policy's `retry_set_item` is set.
+    async fn send_with_retry(
+        &self,
+        method: Method,
+        url: &str,
+        headers: HashMap<String, String>,
+        body: Option<Vec<u8>>,
+        retry_eligible: bool,
+    ) -> Result<HttpResponse> {
+        let Some(policy) = self.options.retry.as_ref().filter(|_| retry_eligible) else {
+            return self.backend.send(method, url, headers, body).await;
+        };
+
+        let mut attempt = 0;
+        let mut delay = policy.base_delay;
+        loop {
+            let result = self
+                .backend
+                .send(method.clone(), url, headers.clone(), body.clone())
+                .await;
+
+            let should_retry = match &result {
+                Ok(response) => policy.is_retryable_status(response.status()),
+                Err(_) => true,
+            };
+
+            if !should_retry || attempt >= policy.max_retries {
+                return result;
+            }
+
+            let wait = match &result {
+                Ok(response) => response
+                    .header("retry-after")
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .map(Duration::from_secs)
+                    .unwrap_or_else(|| policy.jittered_delay(delay)),
+                Err(_) => policy.jittered_delay(delay),
+            };
+
+            tokio::time::sleep(wait).await;
+            delay = policy.next_delay(delay);
+            attempt += 1;
+        }
+    }
+
+    /// Build headers (incl. credentials), send with retry, and on a `401`
+    /// optionally invalidate the credential and retry once.
+    async fn request(
+        &self,
+        method: Method,
+        url: &str,
+        mut headers: HashMap<String, String>,
+        body: Option<Vec<u8>>,
+        retry_eligible: bool,
+    ) -> Result<HttpResponse> {
+        self.apply_credentials(&mut headers).await?;
+        let response = self
+            .send_with_retry(method.clone(), url, headers.clone(), body.clone(), retry_eligible)
+            .await?;
+
+        if response.status() == 401 && self.invalidate_credential_on_401 {
+            if let Some(provider) = &self.credential_provider {
+                provider.invalidate().await;
+                self.apply_credentials(&mut headers).await?;
+                return self.send_with_retry(method, url, headers, body, retry_eligible).await;
+            }
+        }
+
+        Ok(response)
+    }
+
     /// Check if an item exists in the storage by key.
     pub async fn has_item(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<bool> {
         let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::head(url).body(())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let response = request.send_async().await?;
-        Ok(response.status().is_success())
+        let headers = self.get_headers(topts);
+        let response = self.request(Method::HEAD, &url, headers, None, true).await?;
+        Ok(response.is_success())
     }
 
     /// Get an item from the storage by key and return it as a string.
     pub async fn get_item(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<Option<String>> {
-        let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::get(url).body(())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let mut response = request.send_async().await?;
-        if response.status().is_success() {
-            let body = response.text().await?;
-            Ok(Some(body))
-        } else {
-            Ok(None)
-        }
+        let bytes = self.fetch_with_revalidation(key, topts, None).await?;
+        bytes.map(|b| String::from_utf8(b).map_err(Into::into)).transpose()
     }
 
     /// Get an item from the storage by key and deserialize it into the specified type.
@@ -75,13 +227,10 @@ impl UnstorageClient {
         topts: Option<&TransactionOptions>,
     ) -> Result<Option<T>> {
         let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::get(url).body(())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let mut response = request.send_async().await?;
-        if response.status().is_success() {
-            let body = response.text().await?;
-            let deserialized: T = serde_json::from_str(&body)?;
+        let headers = self.get_headers(topts);
+        let response = self.request(Method::GET, &url, headers, None, true).await?;
+        if response.is_success() {
+            let deserialized: T = serde_json::from_str(&response.text()?)?;
             Ok(Some(deserialized))
         } else {
             Ok(None)
@@ -90,74 +239,152 @@ impl UnstorageClient {
 
     /// Get an item in binary mode.
     pub async fn get_item_raw(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<Option<Vec<u8>>> {
-        let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::get(url).body(())?;
+        self.fetch_with_revalidation(key, topts, Some("application/octet-stream")).await
+    }
 
+    /// GET a key, sending `If-None-Match`/`If-Modified-Since` from the last
+    /// successful fetch and reusing that value on a `304 Not Modified`. Also
+    /// honors `Cache-Control: max-age`/`no-store` to skip revalidation
+    /// entirely while a previous fetch is still fresh.
+    async fn fetch_with_revalidation(
+        &self,
+        key: &str,
+        topts: Option<&TransactionOptions>,
+        accept: Option<&str>,
+    ) -> Result<Option<Vec<u8>>> {
+        let url = format!("{}/{}", self.base_url, key);
         let mut headers = self.get_headers(topts);
-        headers.insert("accept".to_string(), "application/octet-stream".to_string());
+        if let Some(accept) = accept {
+            headers.insert("accept".to_string(), accept.to_string());
+        }
+
+        let cached = self.revalidation_cache.lock().unwrap().get(key).cloned();
+        if let Some(entry) = &cached {
+            if !entry.no_store {
+                if let Some(max_age) = entry.max_age {
+                    if entry.fetched_at.elapsed().unwrap_or(max_age) < max_age {
+                        return Ok(Some(entry.value.clone()));
+                    }
+                }
+                if let Some(etag) = &entry.etag {
+                    headers.insert("if-none-match".to_string(), etag.clone());
+                }
+                if let Some(last_modified) = &entry.last_modified {
+                    headers.insert("if-modified-since".to_string(), last_modified.clone());
+                }
+            }
+        }
 
-        for (key, value) in headers {
-            let header_name = HeaderName::from_bytes(key.as_bytes())?;
-            request.headers_mut().insert(header_name, value.parse()?);
+        let response = self.request(Method::GET, &url, headers, None, true).await?;
+
+        if response.status() == 304 {
+            return Ok(cached.map(|entry| {
+                let value = entry.value.clone();
+                self.store_revalidation_entry(key, entry.value, &response, entry.etag, entry.last_modified);
+                value
+            }));
         }
 
-        let mut response = request.send_async().await?;
-        if response.status().is_success() {
-            let body = response.bytes().await?;
-            Ok(Some(body.to_vec()))
+        if response.is_success() {
+            let value = response.bytes().to_vec();
+            self.store_revalidation_entry(key, value.clone(), &response, None, None);
+            Ok(Some(value))
         } else {
             Ok(None)
         }
     }
 
-    /// Get metadata for an item (mtime and ttl from headers).
+    /// Drop any remembered ETag/Last-Modified/cached bytes for `key` so the
+    /// next GET revalidates instead of serving a value a write just made stale.
+    fn invalidate_revalidation_entry(&self, key: &str) {
+        self.revalidation_cache.lock().unwrap().remove(key);
+    }
+
+    fn store_revalidation_entry(
+        &self,
+        key: &str,
+        value: Vec<u8>,
+        response: &HttpResponse,
+        prev_etag: Option<String>,
+        prev_last_modified: Option<String>,
+    ) {
+        let etag = response.header("etag").map(str::to_string).or(prev_etag);
+        let last_modified = response.header("last-modified").map(str::to_string).or(prev_last_modified);
+        let (max_age, no_store) = parse_cache_control(response.header("cache-control"));
+
+        self.revalidation_cache.lock().unwrap().insert(
+            key.to_string(),
+            RevalidationEntry {
+                value,
+                etag,
+                last_modified,
+                fetched_at: SystemTime::now(),
+                max_age,
+                no_store,
+            },
+        );
+    }
+
+    /// Get metadata for an item (mtime, ttl, and etag from headers).
     pub async fn get_meta(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<Option<Meta>> {
         let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::head(url).body(())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let response = request.send_async().await?;
-        if response.status().is_success() {
-            let headers = response.headers();
-            let mtime = headers
-                .get("last-modified")
-                .and_then(|v| v.to_str().ok())
+        let headers = self.get_headers(topts);
+        let response = self.request(Method::HEAD, &url, headers, None, true).await?;
+        if response.is_success() {
+            let mtime = response
+                .header("last-modified")
                 .and_then(|s| DateTime::parse_from_rfc2822(s).ok())
                 .map(|dt| dt.with_timezone(&Utc).into());
 
-            let ttl = headers
-                .get("x-ttl")
-                .and_then(|v| v.to_str().ok())
+            let ttl = response
+                .header("x-ttl")
                 .and_then(|s| s.parse::<u64>().ok())
                 .map(Duration::from_secs);
 
-            Ok(Some(Meta { mtime, ttl }))
+            let etag = response.header("etag").map(str::to_string);
+
+            Ok(Some(Meta { mtime, ttl, etag }))
         } else {
             Ok(None)
         }
     }
 
     /// Set an item in the storage with the given key, value, and optional TTL.
+    ///
+    /// Only retried if the configured [`RetryPolicy`] has `retry_set_item`
+    /// enabled, since PUTs may not be safe to repeat.
     pub async fn set_item(&self, key: &str, value: &str, topts: Option<&TransactionOptions>) -> Result<()> {
         let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::put(url)
-            .header("Content-Type", "application/json")
-            .body(value.to_string())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let _response = request.send_async().await?;
+        let mut headers = self.get_headers(topts);
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+
+        self.request(
+            Method::PUT,
+            &url,
+            headers,
+            Some(value.as_bytes().to_vec()),
+            self.retry_set_item_enabled(),
+        )
+        .await?;
+        self.invalidate_revalidation_entry(key);
         Ok(())
     }
 
     /// Set an item in binary mode.
     pub async fn set_item_raw(&self, key: &str, value: &[u8], topts: Option<&TransactionOptions>) -> Result<()> {
         let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::put(url)
-            .header("Content-Type", "application/octet-stream")
-            .body(value.to_vec())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let _response = request.send_async().await?;
+        let mut headers = self.get_headers(topts);
+        headers.insert("Content-Type".to_string(), "application/octet-stream".to_string());
+
+        self.request(
+            Method::PUT,
+            &url,
+            headers,
+            Some(value.to_vec()),
+            self.retry_set_item_enabled(),
+        )
+        .await?;
+        self.invalidate_revalidation_entry(key);
         Ok(())
     }
 
@@ -169,36 +396,38 @@ impl UnstorageClient {
         topts: Option<&TransactionOptions>,
     ) -> Result<()> {
         let url = format!("{}/{}", self.base_url, key);
+        let mut headers = self.get_headers(topts);
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
         let json_body = serde_json::to_string(value)?;
-        let mut request = Request::put(url)
-            .header("Content-Type", "application/json")
-            .body(json_body)?;
-        self.add_headers_to_request(&mut request, topts)?;
 
-        let _response = request.send_async().await?;
+        self.request(
+            Method::PUT,
+            &url,
+            headers,
+            Some(json_body.into_bytes()),
+            self.retry_set_item_enabled(),
+        )
+        .await?;
+        self.invalidate_revalidation_entry(key);
         Ok(())
     }
 
     /// Remove an item from the storage by key.
     pub async fn remove_item(&self, key: &str, topts: Option<&TransactionOptions>) -> Result<()> {
         let url = format!("{}/{}", self.base_url, key);
-        let mut request = Request::delete(url).body(())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let _response = request.send_async().await?;
+        let headers = self.get_headers(topts);
+        self.request(Method::DELETE, &url, headers, None, true).await?;
+        self.invalidate_revalidation_entry(key);
         Ok(())
     }
 
     /// Get keys from the storage (when the path ends with `/` or `/:`).
     pub async fn get_keys(&self, base: &str, topts: Option<&TransactionOptions>) -> Result<Option<Vec<String>>> {
         let url = format!("{}/{}:", self.base_url, base);
-        let mut request = Request::get(url).body(())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let mut response = request.send_async().await?;
-        if response.status().is_success() {
-            let body = response.text().await?;
-            let keys: Vec<String> = serde_json::from_str(&body)?;
+        let headers = self.get_headers(topts);
+        let response = self.request(Method::GET, &url, headers, None, true).await?;
+        if response.is_success() {
+            let keys: Vec<String> = serde_json::from_str(&response.text()?)?;
             Ok(Some(keys))
         } else {
             Ok(None)
@@ -208,19 +437,81 @@ impl UnstorageClient {
     /// Clear all items in the storage (when the path ends with `/` or `/:`).
     pub async fn clear(&self, base: &str, topts: Option<&TransactionOptions>) -> Result<()> {
         let url = format!("{}/{}:", self.base_url, base);
-        let mut request = Request::delete(url).body(())?;
-        self.add_headers_to_request(&mut request, topts)?;
-
-        let _response = request.send_async().await?;
+        let headers = self.get_headers(topts);
+        self.request(Method::DELETE, &url, headers, None, true).await?;
+        self.revalidation_cache.lock().unwrap().retain(|key, _| !key.starts_with(base));
         Ok(())
     }
+
+    fn retry_set_item_enabled(&self) -> bool {
+        self.options.retry.as_ref().is_some_and(|policy| policy.retry_set_item)
+    }
+
+    /// Get many items at once.
+    ///
+    /// The unstorage HTTP protocol has no batch-get endpoint, so this drives
+    /// `get_item` concurrently, bounded by `ClientOptions::batch_concurrency`.
+    /// One failing key is reported in its slot rather than aborting the rest.
+    pub async fn get_items(
+        &self,
+        keys: &[&str],
+        topts: Option<&TransactionOptions>,
+    ) -> Vec<Result<Option<String>>> {
+        self.run_batch(keys.iter(), |key| self.get_item(key, topts)).await
+    }
+
+    /// Set many items at once.
+    ///
+    /// The unstorage HTTP protocol has no batch-put endpoint, so this drives
+    /// `set_item` concurrently, bounded by `ClientOptions::batch_concurrency`.
+    /// One failing entry is reported in its slot rather than aborting the rest.
+    pub async fn set_items(
+        &self,
+        entries: &[(String, String)],
+        topts: Option<&TransactionOptions>,
+    ) -> Vec<Result<()>> {
+        self.run_batch(entries.iter(), |(key, value)| self.set_item(key, value, topts))
+            .await
+    }
+
+    /// Remove many items at once.
+    ///
+    /// The unstorage HTTP protocol has no batch-delete endpoint, so this
+    /// drives `remove_item` concurrently, bounded by
+    /// `ClientOptions::batch_concurrency`. One failing key is reported in its
+    /// slot rather than aborting the rest.
+    pub async fn remove_items(&self, keys: &[&str], topts: Option<&TransactionOptions>) -> Vec<Result<()>> {
+        self.run_batch(keys.iter(), |key| self.remove_item(key, topts)).await
+    }
+
+    /// Drive `make_future(item)` for every item in `items`, bounded by
+    /// `ClientOptions::batch_concurrency`, preserving input order in the
+    /// returned results.
+    async fn run_batch<I, F, Fut, T>(&self, items: I, make_future: F) -> Vec<Result<T>>
+    where
+        I: Iterator,
+        F: Fn(I::Item) -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let semaphore = tokio::sync::Semaphore::new(self.options.batch_concurrency.max(1));
+        let futures = items.map(|item| {
+            let semaphore = &semaphore;
+            let future = make_future(item);
+            async move {
+                let _permit = semaphore.acquire().await.expect("semaphore is never closed");
+                future.await
+            }
+        });
+        futures::future::join_all(futures).await
+    }
 }
 
-/// Metadata for an item (mtime and ttl).
+/// Metadata for an item (mtime, ttl, and etag).
 #[derive(Debug)]
 pub struct Meta {
     pub mtime: Option<SystemTime>,
     pub ttl: Option<Duration>,
+    pub etag: Option<String>,
 }
 
 /// Transaction options for storage operations.
@@ -243,18 +534,35 @@ mod tests {
 
     #[tokio::test]
     async fn test_unstorage_client() {
-        let client = UnstorageClient::new("http://localhost:3000".to_string(), None);
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(200, HashMap::new(), Vec::new()));
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"Hello, World!".to_vec()));
+
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend);
         let key = "test";
         let value = "Hello, World!";
 
         client.set_item(key, value, None).await.unwrap();
         let item = client.get_item(key, None).await.unwrap().unwrap();
         assert_eq!(item, value);
+
+        let requests = client.backend.requests();
+        assert_eq!(requests[0].method, Method::PUT);
+        assert_eq!(requests[0].body.as_deref(), Some(value.as_bytes()));
+        assert_eq!(requests[1].method, Method::GET);
     }
 
     #[tokio::test]
     async fn test_set_and_get_item_json() {
-        let client = UnstorageClient::new("http://localhost:3000".to_string(), None);
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(200, HashMap::new(), Vec::new()));
+        backend.push_response(HttpResponse::new(
+            200,
+            HashMap::new(),
+            br#"{"name":"Alice","age":30}"#.to_vec(),
+        ));
+
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend);
         let key = "test_json";
         let value = TestData {
             name: "Alice".to_string(),
@@ -269,4 +577,162 @@ mod tests {
         assert!(retrieved.is_some());
         assert_eq!(retrieved.unwrap(), value);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_get_item_with_mock_backend() {
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(
+            200,
+            HashMap::new(),
+            b"Hello, Mock!".to_vec(),
+        ));
+
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend);
+        let item = client.get_item("test", None).await.unwrap().unwrap();
+        assert_eq!(item, "Hello, Mock!");
+    }
+
+    #[tokio::test]
+    async fn test_set_item_invalidates_fresh_revalidation_entry() {
+        let backend = MockBackend::new();
+        let mut fresh_headers = HashMap::new();
+        fresh_headers.insert("cache-control".to_string(), "max-age=60".to_string());
+        backend.push_response(HttpResponse::new(200, fresh_headers, b"v1".to_vec()));
+        backend.push_response(HttpResponse::new(200, HashMap::new(), Vec::new()));
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"v2".to_vec()));
+
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend);
+
+        let first = client.get_item("test", None).await.unwrap().unwrap();
+        assert_eq!(first, "v1");
+
+        client.set_item("test", "v2", None).await.unwrap();
+
+        // Without invalidating the revalidation cache this would still be
+        // served from the (still "fresh" per max-age) cache as "v1".
+        let second = client.get_item("test", None).await.unwrap().unwrap();
+        assert_eq!(second, "v2");
+    }
+
+    #[tokio::test]
+    async fn test_revalidating_get_sends_conditional_headers() {
+        let backend = MockBackend::new();
+        let mut etag_headers = HashMap::new();
+        etag_headers.insert("etag".to_string(), "\"v1\"".to_string());
+        etag_headers.insert("last-modified".to_string(), "Mon, 01 Jan 2024 00:00:00 GMT".to_string());
+        backend.push_response(HttpResponse::new(200, etag_headers, b"v1".to_vec()));
+        backend.push_response(HttpResponse::new(304, HashMap::new(), Vec::new()));
+
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend);
+
+        let first = client.get_item("test", None).await.unwrap().unwrap();
+        assert_eq!(first, "v1");
+
+        let second = client.get_item("test", None).await.unwrap().unwrap();
+        assert_eq!(second, "v1");
+
+        let requests = client.backend.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].headers.get("if-none-match"), None);
+        assert_eq!(requests[1].headers.get("if-none-match"), Some(&"\"v1\"".to_string()));
+        assert_eq!(
+            requests[1].headers.get("if-modified-since"),
+            Some(&"Mon, 01 Jan 2024 00:00:00 GMT".to_string())
+        );
+    }
+
+    struct CountingInvalidateProvider {
+        invalidations: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl CredentialProvider for CountingInvalidateProvider {
+        async fn authorization_header(&self) -> Result<Option<String>> {
+            let invalidations = self.invalidations.load(std::sync::atomic::Ordering::SeqCst);
+            Ok(Some(format!("Bearer token-{invalidations}")))
+        }
+
+        async fn invalidate(&self) {
+            self.invalidations.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_401_invalidates_credential_and_retries_once() {
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(401, HashMap::new(), Vec::new()));
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"Hello, Mock!".to_vec()));
+
+        let invalidations = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let provider = CountingInvalidateProvider {
+            invalidations: invalidations.clone(),
+        };
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend)
+            .with_credential_provider(provider, true);
+
+        let item = client.get_item("test", None).await.unwrap().unwrap();
+        assert_eq!(item, "Hello, Mock!");
+        assert_eq!(invalidations.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // The retried request must carry the refreshed credential, not a
+        // replay of the one that just got a 401.
+        let requests = client.backend.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].headers.get("authorization"), Some(&"Bearer token-0".to_string()));
+        assert_eq!(requests[1].headers.get("authorization"), Some(&"Bearer token-1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_401_without_invalidate_on_401_is_not_retried() {
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(401, HashMap::new(), Vec::new()));
+
+        let invalidations = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let provider = CountingInvalidateProvider {
+            invalidations: invalidations.clone(),
+        };
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend)
+            .with_credential_provider(provider, false);
+
+        let item = client.get_item("test", None).await.unwrap();
+        assert_eq!(item, None);
+        assert_eq!(invalidations.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_items_preserves_order_with_serial_concurrency() {
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"1".to_vec()));
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"2".to_vec()));
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"3".to_vec()));
+
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend)
+            .with_options(ClientOptions {
+                batch_concurrency: 1,
+                ..ClientOptions::default()
+            });
+
+        let results = client.get_items(&["a", "b", "c"], None).await;
+        let values: Vec<String> = results.into_iter().map(|r| r.unwrap().unwrap()).collect();
+        assert_eq!(values, vec!["1", "2", "3"]);
+    }
+
+    #[tokio::test]
+    async fn test_get_items_one_failure_does_not_abort_the_rest() {
+        let backend = MockBackend::new();
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"1".to_vec()));
+        backend.push_response(HttpResponse::new(200, HashMap::new(), b"2".to_vec()));
+        // No third response queued: the third key's fetch errors.
+
+        let client = UnstorageClient::with_backend("http://localhost:3000".to_string(), None, backend)
+            .with_options(ClientOptions {
+                batch_concurrency: 1,
+                ..ClientOptions::default()
+            });
+
+        let mut results = client.get_items(&["a", "b", "c"], None).await.into_iter();
+        assert_eq!(results.next().unwrap().unwrap(), Some("1".to_string()));
+        assert_eq!(results.next().unwrap().unwrap(), Some("2".to_string()));
+        assert!(results.next().unwrap().is_err());
+    }
+}
@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use rand::Rng;
+
+/// Controls whether and how `UnstorageClient` retries failed requests.
+///
+/// By default only idempotent operations (`has_item`, `get_item*`, `get_meta`,
+/// `get_keys`, `remove_item`, `clear`) are retried. Set `retry_set_item` to
+/// also retry `set_item*` calls, which is only safe if the server treats PUTs
+/// as idempotent.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    pub retry_set_item: bool,
+}
+
+impl RetryPolicy {
+    /// A retry policy with the given retry budget and the repo's defaults
+    /// otherwise: a 1s base delay doubling up to a 30s cap.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: u16) -> bool {
+        matches!(status, 500 | 502 | 503 | 504 | 429)
+    }
+
+    /// Add random jitter in `[0, delay/2)` to avoid thundering herds.
+    pub(crate) fn jittered_delay(&self, delay: Duration) -> Duration {
+        let max_jitter_ms = (delay.as_millis() as u64) / 2;
+        let jitter_ms = if max_jitter_ms > 0 {
+            rand::thread_rng().gen_range(0..max_jitter_ms)
+        } else {
+            0
+        };
+        delay + Duration::from_millis(jitter_ms)
+    }
+
+    pub(crate) fn next_delay(&self, delay: Duration) -> Duration {
+        (delay * 2).min(self.max_delay)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+            retry_set_item: false,
+        }
+    }
+}
+
+/// Options controlling `UnstorageClient` behavior beyond the base URL and
+/// headers, such as retry policy and batch concurrency.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    pub retry: Option<RetryPolicy>,
+    /// Max number of requests a batch method (`get_items`, `set_items`,
+    /// `remove_items`) drives concurrently.
+    pub batch_concurrency: usize,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            retry: None,
+            batch_concurrency: 10,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_retryable_status() {
+        let policy = RetryPolicy::default();
+        for status in [500, 502, 503, 504, 429] {
+            assert!(policy.is_retryable_status(status), "{status} should be retryable");
+        }
+        for status in [200, 400, 401, 404] {
+            assert!(!policy.is_retryable_status(status), "{status} should not be retryable");
+        }
+    }
+
+    #[test]
+    fn test_jittered_delay_adds_bounded_jitter() {
+        let policy = RetryPolicy::default();
+        let delay = Duration::from_millis(1000);
+        for _ in 0..100 {
+            let jittered = policy.jittered_delay(delay);
+            assert!(jittered >= delay);
+            assert!(jittered < delay + delay / 2);
+        }
+    }
+
+    #[test]
+    fn test_next_delay_doubles_and_caps_at_max_delay() {
+        let policy = RetryPolicy {
+            max_delay: Duration::from_secs(5),
+            ..RetryPolicy::default()
+        };
+        assert_eq!(policy.next_delay(Duration::from_secs(1)), Duration::from_secs(2));
+        assert_eq!(policy.next_delay(Duration::from_secs(4)), Duration::from_secs(5));
+        assert_eq!(policy.next_delay(Duration::from_secs(5)), Duration::from_secs(5));
+    }
+}